@@ -1,11 +1,14 @@
+use std::io::{self, Write};
 use nom::branch::alt;
-use nom::multi::many1;
-use nom::error::Error;
-use nom::{Finish, IResult};
+use nom::multi::{many1, separated_list0};
+use nom::error::{Error, ErrorKind};
+use nom::{Finish, IResult, Needed};
 use nom::bytes::complete::{is_a, take, take_while1};
+use nom::bytes::streaming::take as take_streaming;
 use nom::sequence::{preceded, terminated, tuple};
 use nom::combinator::{map, map_res, opt, recognize};
-use nom::character::complete::{char, crlf, digit1, none_of, one_of};
+use nom::character::complete::{char, crlf, digit1, none_of, one_of, space0, space1};
+use nom::character::streaming::{char as char_streaming, crlf as crlf_streaming, digit1 as digit1_streaming, none_of as none_of_streaming, one_of as one_of_streaming};
 
 
 #[derive(Debug, PartialEq)]
@@ -13,7 +16,7 @@ pub enum Resp2Type {
     SimpleString(String),
     SimpleError(Resp2SimpleError),
     Integer(i64),
-    BulkString(String),
+    BulkString(Vec<u8>),
     NullBulkString,
     Array(Vec<Resp2Type>),
     NullArray
@@ -21,16 +24,56 @@ pub enum Resp2Type {
 
 
 #[derive(Debug, PartialEq)]
-struct Resp2SimpleError {
-    kind: String,
-    message: String
+pub struct Resp2SimpleError {
+    pub kind: String,
+    pub message: String
 }
 
 
+#[derive(Debug, PartialEq)]
+pub enum ParseResult<'a> {
+    Complete { value: Resp2Type, consumed: usize },
+    Incomplete { needed: Needed },
+    Invalid(Error<&'a [u8]>)
+}
+
+
+#[derive(Debug, PartialEq)]
+pub enum Resp3Type {
+    SimpleString(String),
+    SimpleError(Resp2SimpleError),
+    Integer(i64),
+    BulkString(Vec<u8>),
+    NullBulkString,
+    Array(Vec<Resp3Type>),
+    NullArray,
+    Null,
+    Boolean(bool),
+    Double(f64),
+    BigNumber(String),
+    VerbatimString { format: [u8; 3], data: Vec<u8> },
+    BlobError(Vec<u8>),
+    Map(Vec<(Resp3Type, Resp3Type)>),
+    Set(Vec<Resp3Type>),
+    Push(Vec<Resp3Type>)
+}
+
+
+type InlineEscapeResult<'a> = Result<(u8, &'a [u8]), nom::Err<Error<&'a [u8]>>>;
+
+
 pub struct Resp2Codec;
 
 impl Resp2Codec {
     pub fn parse(input: &str) -> Result<Resp2Type, Error<&str>> {
+        Self::parse_bytes(input.as_bytes()).map_err(|error| Error {
+            input: std::str::from_utf8(error.input).unwrap_or(input),
+            code: error.code
+        })
+    }
+
+
+    pub fn parse_bytes(input: &[u8]) -> Result<Resp2Type, Error<&[u8]>> {
         let parsing_result = Self::parse_internal(input);
 
         match parsing_result.finish() {
@@ -40,7 +83,7 @@ impl Resp2Codec {
     }
 
 
-    fn parse_internal(input: &str) -> IResult<&str, Resp2Type> {
+    fn parse_internal(input: &[u8]) -> IResult<&[u8], Resp2Type> {
         let (input, first_character) = one_of("+-:$*")(input)?;
 
         match first_character {
@@ -54,21 +97,21 @@ impl Resp2Codec {
     }
 
 
-    fn parse_simple_string(input: &str) -> IResult<&str, Resp2Type> {
-        map(
-            tuple((many1(none_of("\r\n")), char('\r'), char('\n'))),
-            |(character_vector, _, _)| Resp2Type::SimpleString(character_vector.into_iter().collect::<String>())
+    fn parse_simple_string(input: &[u8]) -> IResult<&[u8], Resp2Type> {
+        map_res(
+            terminated(recognize(many1(none_of("\r\n"))), crlf),
+            |bytes: &[u8]| std::str::from_utf8(bytes).map(|s| Resp2Type::SimpleString(s.to_string()))
         )(input)
     }
 
 
-    fn parse_simple_error(input: &str) -> IResult<&str, Resp2Type> {
+    fn parse_simple_error(input: &[u8]) -> IResult<&[u8], Resp2Type> {
         map(
             tuple(
-            (take_while1::<_, &str, _>(|c| c.is_ascii_uppercase()), preceded(is_a(" \n"), Self::parse_simple_string))
+            (take_while1::<_, &[u8], _>(|c: u8| c.is_ascii_uppercase()), preceded(is_a(" \n"), Self::parse_simple_string))
             ),
             |(kind, simple_string)| Resp2Type::SimpleError(Resp2SimpleError {
-                kind: kind.to_string(),
+                kind: std::str::from_utf8(kind).unwrap().to_string(),
                 message: match simple_string {
                     Resp2Type::SimpleString(msg) => msg,
                     _ => panic!("Expected Resp2Type::SimpleString")
@@ -78,28 +121,28 @@ impl Resp2Codec {
     }
 
 
-    fn parse_int(input: &str) -> IResult<&str, Resp2Type> {
+    fn parse_int(input: &[u8]) -> IResult<&[u8], Resp2Type> {
         let result = map_res(
             terminated(
                 recognize(
                     tuple(
-                        (opt(alt((char::<&str, _>('+'), char::<&str, _>('-')))), digit1)
+                        (opt(alt((char::<&[u8], _>('+'), char::<&[u8], _>('-')))), digit1)
                     ),
                 ),
                 crlf
             ),
-            |digits| digits.parse::<i64>()
+            |digits: &[u8]| std::str::from_utf8(digits).ok().and_then(|s| s.parse::<i64>().ok()).ok_or(())
         )(input);
 
         result.map(|(rest, number)| (rest, Resp2Type::Integer(number)))
     }
 
 
-    fn parse_bulk_string(input: &str) -> IResult<&str, Resp2Type> {
+    fn parse_bulk_string(input: &[u8]) -> IResult<&[u8], Resp2Type> {
         let (input, length) = terminated(
             map_res(
                 recognize(tuple((opt(alt((char('+'), char('-')))), digit1))),
-                |digits: &str| digits.parse::<isize>()
+                |digits: &[u8]| std::str::from_utf8(digits).ok().and_then(|s| s.parse::<isize>().ok()).ok_or(())
             ),
             crlf
         )(input)?;
@@ -112,15 +155,15 @@ impl Resp2Codec {
         let (input, data) = take(length)(input)?;
         let (input, _) = crlf(input)?;
 
-        Ok((input, Resp2Type::BulkString(data.to_string())))
+        Ok((input, Resp2Type::BulkString(data.to_vec())))
     }
 
 
-    fn parse_array(input: &str) -> IResult<&str, Resp2Type> {
+    fn parse_array(input: &[u8]) -> IResult<&[u8], Resp2Type> {
         let (input, length) = terminated(
             map_res(
                 recognize(tuple((opt(alt((char('+'), char('-')))), digit1))),
-                |digits: &str| digits.parse::<isize>()
+                |digits: &[u8]| std::str::from_utf8(digits).ok().and_then(|s| s.parse::<isize>().ok()).ok_or(())
             ),
             crlf
         )(input)?;
@@ -141,12 +184,495 @@ impl Resp2Codec {
 
         Ok((input, Resp2Type::Array(elements)))
     }
+
+
+    pub fn parse_streaming(input: &[u8]) -> ParseResult<'_> {
+        match Self::parse_streaming_internal(input) {
+            Ok((remaining, value)) => ParseResult::Complete { value, consumed: input.len() - remaining.len() },
+            Err(nom::Err::Incomplete(needed)) => ParseResult::Incomplete { needed },
+            Err(nom::Err::Error(error)) | Err(nom::Err::Failure(error)) => ParseResult::Invalid(error)
+        }
+    }
+
+
+    fn parse_streaming_internal(input: &[u8]) -> IResult<&[u8], Resp2Type> {
+        let (input, first_character) = one_of_streaming("+-:$*")(input)?;
+
+        match first_character {
+            '+' => Self::parse_streaming_simple_string(input),
+            '-' => Self::parse_streaming_simple_error(input),
+            ':' => Self::parse_streaming_int(input),
+            '$' => Self::parse_streaming_bulk_string(input),
+            '*' => Self::parse_streaming_array(input),
+            _ => unreachable!()
+        }
+    }
+
+
+    fn parse_streaming_simple_string(input: &[u8]) -> IResult<&[u8], Resp2Type> {
+        map_res(
+            terminated(recognize(many1(none_of_streaming("\r\n"))), crlf_streaming),
+            |bytes: &[u8]| std::str::from_utf8(bytes).map(|s| Resp2Type::SimpleString(s.to_string()))
+        )(input)
+    }
+
+
+    fn parse_streaming_simple_error(input: &[u8]) -> IResult<&[u8], Resp2Type> {
+        map(
+            tuple(
+            (take_while1::<_, &[u8], _>(|c: u8| c.is_ascii_uppercase()), preceded(is_a(" \n"), Self::parse_streaming_simple_string))
+            ),
+            |(kind, simple_string)| Resp2Type::SimpleError(Resp2SimpleError {
+                kind: std::str::from_utf8(kind).unwrap().to_string(),
+                message: match simple_string {
+                    Resp2Type::SimpleString(msg) => msg,
+                    _ => panic!("Expected Resp2Type::SimpleString")
+                }
+            })
+        )(input)
+    }
+
+
+    fn parse_streaming_int(input: &[u8]) -> IResult<&[u8], Resp2Type> {
+        let result = map_res(
+            terminated(
+                recognize(
+                    tuple(
+                        (opt(alt((char_streaming::<&[u8], _>('+'), char_streaming::<&[u8], _>('-')))), digit1_streaming)
+                    ),
+                ),
+                crlf_streaming
+            ),
+            |digits: &[u8]| std::str::from_utf8(digits).ok().and_then(|s| s.parse::<i64>().ok()).ok_or(())
+        )(input);
+
+        result.map(|(rest, number)| (rest, Resp2Type::Integer(number)))
+    }
+
+
+    fn parse_streaming_length_header(input: &[u8]) -> IResult<&[u8], isize> {
+        terminated(
+            map_res(
+                recognize(tuple((opt(alt((char_streaming('+'), char_streaming('-')))), digit1_streaming))),
+                |digits: &[u8]| std::str::from_utf8(digits).ok().and_then(|s| s.parse::<isize>().ok()).ok_or(())
+            ),
+            crlf_streaming
+        )(input)
+    }
+
+
+    fn parse_streaming_bulk_string(input: &[u8]) -> IResult<&[u8], Resp2Type> {
+        let (input, length) = Self::parse_streaming_length_header(input)?;
+
+        if length == -1 {
+            return Ok((input, Resp2Type::NullBulkString));
+        }
+
+        if length < 0 {
+            return Err(nom::Err::Error(Error { input, code: ErrorKind::LengthValue }));
+        }
+
+        let length = length as usize;
+        let required = length + 2;
+
+        if input.len() < required {
+            return Err(nom::Err::Incomplete(Needed::new(required - input.len())));
+        }
+
+        let (input, data) = take_streaming(length)(input)?;
+        let (input, _) = crlf_streaming(input)?;
+
+        Ok((input, Resp2Type::BulkString(data.to_vec())))
+    }
+
+
+    fn parse_streaming_array(input: &[u8]) -> IResult<&[u8], Resp2Type> {
+        let (input, length) = Self::parse_streaming_length_header(input)?;
+
+        if length == -1 {
+            return Ok((input, Resp2Type::NullArray))
+        }
+
+        let length = length as usize;
+        let mut input = input;
+        let mut elements = Vec::new();
+
+        for _ in 0..length {
+            let (new_input, element) = Self::parse_streaming_internal(input)?;
+            input = new_input;
+            elements.push(element);
+        }
+
+        Ok((input, Resp2Type::Array(elements)))
+    }
+
+
+    pub fn encode(value: &Resp2Type) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        Self::write_to(value, &mut buffer).expect("writing to a Vec<u8> never fails");
+        buffer
+    }
+
+
+    pub fn write_to<W: Write>(value: &Resp2Type, writer: &mut W) -> io::Result<()> {
+        match value {
+            Resp2Type::SimpleString(string) => write!(writer, "+{}\r\n", string),
+            Resp2Type::SimpleError(error) => write!(writer, "-{} {}\r\n", error.kind, error.message),
+            Resp2Type::Integer(number) => write!(writer, ":{}\r\n", number),
+            Resp2Type::BulkString(data) => {
+                write!(writer, "${}\r\n", data.len())?;
+                writer.write_all(data)?;
+                writer.write_all(b"\r\n")
+            }
+            Resp2Type::NullBulkString => writer.write_all(b"$-1\r\n"),
+            Resp2Type::Array(elements) => {
+                write!(writer, "*{}\r\n", elements.len())?;
+
+                for element in elements {
+                    Self::write_to(element, writer)?;
+                }
+
+                Ok(())
+            }
+            Resp2Type::NullArray => writer.write_all(b"*-1\r\n")
+        }
+    }
+
+
+    pub fn parse_resp3(input: &[u8]) -> Result<Resp3Type, Error<&[u8]>> {
+        match Self::parse_resp3_internal(input).finish() {
+            Ok((_, value)) => Ok(value),
+            Err(error) => Err(error)
+        }
+    }
+
+
+    fn parse_resp3_internal(input: &[u8]) -> IResult<&[u8], Resp3Type> {
+        let (input, first_character) = one_of("+-:$*_#,(=!%~>")(input)?;
+
+        match first_character {
+            '+' => Self::parse_resp3_simple_string(input),
+            '-' => Self::parse_resp3_simple_error(input),
+            ':' => Self::parse_resp3_int(input),
+            '$' => Self::parse_resp3_bulk_string(input),
+            '*' => Self::parse_resp3_array(input),
+            '_' => Self::parse_resp3_null(input),
+            '#' => Self::parse_resp3_boolean(input),
+            ',' => Self::parse_resp3_double(input),
+            '(' => Self::parse_resp3_big_number(input),
+            '=' => Self::parse_resp3_verbatim_string(input),
+            '!' => Self::parse_resp3_blob_error(input),
+            '%' => Self::parse_resp3_map(input),
+            '~' => Self::parse_resp3_set(input),
+            '>' => Self::parse_resp3_push(input),
+            _ => unreachable!()
+        }
+    }
+
+
+    fn parse_signed_length(input: &[u8]) -> IResult<&[u8], isize> {
+        terminated(
+            map_res(
+                recognize(tuple((opt(alt((char('+'), char('-')))), digit1))),
+                |digits: &[u8]| std::str::from_utf8(digits).ok().and_then(|s| s.parse::<isize>().ok()).ok_or(())
+            ),
+            crlf
+        )(input)
+    }
+
+
+    fn parse_unsigned_length(input: &[u8]) -> IResult<&[u8], usize> {
+        terminated(
+            map_res(digit1, |digits: &[u8]| std::str::from_utf8(digits).ok().and_then(|s| s.parse::<usize>().ok()).ok_or(())),
+            crlf
+        )(input)
+    }
+
+
+    fn parse_resp3_simple_string(input: &[u8]) -> IResult<&[u8], Resp3Type> {
+        map_res(
+            terminated(recognize(many1(none_of("\r\n"))), crlf),
+            |bytes: &[u8]| std::str::from_utf8(bytes).map(|s| Resp3Type::SimpleString(s.to_string()))
+        )(input)
+    }
+
+
+    fn parse_resp3_simple_error(input: &[u8]) -> IResult<&[u8], Resp3Type> {
+        map(
+            tuple(
+            (take_while1::<_, &[u8], _>(|c: u8| c.is_ascii_uppercase()), preceded(is_a(" \n"), Self::parse_resp3_simple_string))
+            ),
+            |(kind, simple_string)| Resp3Type::SimpleError(Resp2SimpleError {
+                kind: std::str::from_utf8(kind).unwrap().to_string(),
+                message: match simple_string {
+                    Resp3Type::SimpleString(msg) => msg,
+                    _ => panic!("Expected Resp3Type::SimpleString")
+                }
+            })
+        )(input)
+    }
+
+
+    fn parse_resp3_int(input: &[u8]) -> IResult<&[u8], Resp3Type> {
+        let result = map_res(
+            terminated(
+                recognize(tuple((opt(alt((char::<&[u8], _>('+'), char::<&[u8], _>('-')))), digit1))),
+                crlf
+            ),
+            |digits: &[u8]| std::str::from_utf8(digits).ok().and_then(|s| s.parse::<i64>().ok()).ok_or(())
+        )(input);
+
+        result.map(|(rest, number)| (rest, Resp3Type::Integer(number)))
+    }
+
+
+    fn parse_resp3_bulk_string(input: &[u8]) -> IResult<&[u8], Resp3Type> {
+        let (input, length) = Self::parse_signed_length(input)?;
+
+        if length == -1 {
+            return Ok((input, Resp3Type::NullBulkString));
+        }
+
+        let (input, data) = take(length as usize)(input)?;
+        let (input, _) = crlf(input)?;
+
+        Ok((input, Resp3Type::BulkString(data.to_vec())))
+    }
+
+
+    fn parse_resp3_array(input: &[u8]) -> IResult<&[u8], Resp3Type> {
+        let (input, length) = Self::parse_signed_length(input)?;
+
+        if length == -1 {
+            return Ok((input, Resp3Type::NullArray));
+        }
+
+        let (input, elements) = Self::parse_resp3_elements(input, length as usize)?;
+
+        Ok((input, Resp3Type::Array(elements)))
+    }
+
+
+    fn parse_resp3_elements(input: &[u8], length: usize) -> IResult<&[u8], Vec<Resp3Type>> {
+        let mut input = input;
+        let mut elements = Vec::new();
+
+        for _ in 0..length {
+            let (new_input, element) = Self::parse_resp3_internal(input)?;
+            input = new_input;
+            elements.push(element);
+        }
+
+        Ok((input, elements))
+    }
+
+
+    fn parse_resp3_null(input: &[u8]) -> IResult<&[u8], Resp3Type> {
+        map(crlf, |_| Resp3Type::Null)(input)
+    }
+
+
+    fn parse_resp3_boolean(input: &[u8]) -> IResult<&[u8], Resp3Type> {
+        map(
+            terminated(one_of("tf"), crlf),
+            |flag| Resp3Type::Boolean(flag == 't')
+        )(input)
+    }
+
+
+    fn parse_resp3_double(input: &[u8]) -> IResult<&[u8], Resp3Type> {
+        map_res(
+            terminated(recognize(many1(none_of("\r\n"))), crlf),
+            |token: &[u8]| std::str::from_utf8(token).ok().and_then(|s| s.parse::<f64>().ok()).ok_or(())
+        )(input).map(|(rest, value)| (rest, Resp3Type::Double(value)))
+    }
+
+
+    fn parse_resp3_big_number(input: &[u8]) -> IResult<&[u8], Resp3Type> {
+        map(
+            terminated(recognize(tuple((opt(alt((char('+'), char('-')))), digit1))), crlf),
+            |digits: &[u8]| Resp3Type::BigNumber(std::str::from_utf8(digits).unwrap().to_string())
+        )(input)
+    }
+
+
+    fn parse_resp3_verbatim_string(input: &[u8]) -> IResult<&[u8], Resp3Type> {
+        let (input, length) = Self::parse_signed_length(input)?;
+
+        if length < 4 {
+            return Err(nom::Err::Error(Error { input, code: ErrorKind::LengthValue }));
+        }
+
+        let length = length as usize;
+        let (input, format_bytes) = take(3usize)(input)?;
+        let (input, _) = char(':')(input)?;
+        let (input, data) = take(length - 4)(input)?;
+        let (input, _) = crlf(input)?;
+
+        Ok((input, Resp3Type::VerbatimString {
+            format: [format_bytes[0], format_bytes[1], format_bytes[2]],
+            data: data.to_vec()
+        }))
+    }
+
+
+    fn parse_resp3_blob_error(input: &[u8]) -> IResult<&[u8], Resp3Type> {
+        let (input, length) = Self::parse_signed_length(input)?;
+
+        if length < 0 {
+            return Err(nom::Err::Error(Error { input, code: ErrorKind::LengthValue }));
+        }
+
+        let (input, data) = take(length as usize)(input)?;
+        let (input, _) = crlf(input)?;
+
+        Ok((input, Resp3Type::BlobError(data.to_vec())))
+    }
+
+
+    fn parse_resp3_map(input: &[u8]) -> IResult<&[u8], Resp3Type> {
+        let (input, length) = Self::parse_unsigned_length(input)?;
+        let (input, elements) = Self::parse_resp3_elements(input, length * 2)?;
+
+        let mut elements = elements.into_iter();
+        let mut pairs = Vec::with_capacity(length);
+
+        while let (Some(key), Some(value)) = (elements.next(), elements.next()) {
+            pairs.push((key, value));
+        }
+
+        Ok((input, Resp3Type::Map(pairs)))
+    }
+
+
+    fn parse_resp3_set(input: &[u8]) -> IResult<&[u8], Resp3Type> {
+        let (input, length) = Self::parse_unsigned_length(input)?;
+        let (input, elements) = Self::parse_resp3_elements(input, length)?;
+
+        Ok((input, Resp3Type::Set(elements)))
+    }
+
+
+    fn parse_resp3_push(input: &[u8]) -> IResult<&[u8], Resp3Type> {
+        let (input, length) = Self::parse_unsigned_length(input)?;
+        let (input, elements) = Self::parse_resp3_elements(input, length)?;
+
+        Ok((input, Resp3Type::Push(elements)))
+    }
+
+
+    pub fn parse_inline(input: &[u8]) -> Result<Resp2Type, Error<&[u8]>> {
+        match Self::parse_inline_internal(input).finish() {
+            Ok((_, value)) => Ok(value),
+            Err(error) => Err(error)
+        }
+    }
+
+
+    fn parse_inline_internal(input: &[u8]) -> IResult<&[u8], Resp2Type> {
+        if matches!(input.first(), Some(b'+') | Some(b'-') | Some(b':') | Some(b'$') | Some(b'*')) {
+            return Err(nom::Err::Error(Error { input, code: ErrorKind::Verify }));
+        }
+
+        let (input, _) = space0(input)?;
+        let (input, tokens) = separated_list0(space1, Self::parse_inline_token)(input)?;
+        let (input, _) = space0(input)?;
+        let (input, _) = crlf(input)?;
+
+        Ok((input, Resp2Type::Array(tokens.into_iter().map(Resp2Type::BulkString).collect())))
+    }
+
+
+    fn parse_inline_token(input: &[u8]) -> IResult<&[u8], Vec<u8>> {
+        alt((Self::parse_inline_double_quoted, Self::parse_inline_single_quoted, Self::parse_inline_unquoted))(input)
+    }
+
+
+    fn parse_inline_unquoted(input: &[u8]) -> IResult<&[u8], Vec<u8>> {
+        map(
+            take_while1(|c: u8| !matches!(c, b' ' | b'\t' | b'\r' | b'\n' | b'"' | b'\'')),
+            |bytes: &[u8]| bytes.to_vec()
+        )(input)
+    }
+
+
+    fn parse_inline_single_quoted(input: &[u8]) -> IResult<&[u8], Vec<u8>> {
+        let (mut input, _) = char('\'')(input)?;
+        let mut token = Vec::new();
+
+        loop {
+            match input.first() {
+                None | Some(b'\r') | Some(b'\n') => return Err(nom::Err::Error(Error { input, code: ErrorKind::Char })),
+                Some(b'\'') => {
+                    input = &input[1..];
+                    break;
+                }
+                Some(b'\\') if matches!(input.get(1), Some(b'\'') | Some(b'\\')) => {
+                    token.push(input[1]);
+                    input = &input[2..];
+                }
+                Some(&byte) => {
+                    token.push(byte);
+                    input = &input[1..];
+                }
+            }
+        }
+
+        Ok((input, token))
+    }
+
+
+    fn parse_inline_double_quoted(input: &[u8]) -> IResult<&[u8], Vec<u8>> {
+        let (mut input, _) = char('"')(input)?;
+        let mut token = Vec::new();
+
+        loop {
+            match input.first() {
+                None | Some(b'\r') | Some(b'\n') => return Err(nom::Err::Error(Error { input, code: ErrorKind::Char })),
+                Some(b'"') => {
+                    input = &input[1..];
+                    break;
+                }
+                Some(b'\\') => {
+                    let (byte, rest) = Self::parse_inline_escape(&input[1..])?;
+                    token.push(byte);
+                    input = rest;
+                }
+                Some(&byte) => {
+                    token.push(byte);
+                    input = &input[1..];
+                }
+            }
+        }
+
+        Ok((input, token))
+    }
+
+
+    fn parse_inline_escape(input: &[u8]) -> InlineEscapeResult<'_> {
+        match input.first() {
+            Some(b'n') => Ok((b'\n', &input[1..])),
+            Some(b'r') => Ok((b'\r', &input[1..])),
+            Some(b't') => Ok((b'\t', &input[1..])),
+            Some(b'\\') => Ok((b'\\', &input[1..])),
+            Some(b'"') => Ok((b'"', &input[1..])),
+            Some(b'x') => {
+                let hex = input.get(1..3).ok_or(nom::Err::Error(Error { input, code: ErrorKind::HexDigit }))?;
+                let digits = std::str::from_utf8(hex).map_err(|_| nom::Err::Error(Error { input, code: ErrorKind::HexDigit }))?;
+                let byte = u8::from_str_radix(digits, 16).map_err(|_| nom::Err::Error(Error { input, code: ErrorKind::HexDigit }))?;
+
+                Ok((byte, &input[3..]))
+            }
+            _ => Err(nom::Err::Error(Error { input, code: ErrorKind::EscapedTransform }))
+        }
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
-    use crate::{Resp2Codec, Resp2SimpleError, Resp2Type};
+    use nom::Needed;
+    use crate::{ParseResult, Resp2Codec, Resp2SimpleError, Resp2Type, Resp3Type};
 
 
     #[test]
@@ -193,6 +719,27 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_simple_string_preserves_non_ascii_utf8() {
+        assert_eq!(
+            Resp2Codec::parse("+h\u{e9}llo\r\n"),
+            Ok(Resp2Type::SimpleString("h\u{e9}llo".to_string()))
+        );
+
+        assert_eq!(
+            Resp2Codec::parse_streaming("+h\u{e9}llo\r\n".as_bytes()),
+            ParseResult::Complete { value: Resp2Type::SimpleString("h\u{e9}llo".to_string()), consumed: 9 }
+        );
+
+        assert_eq!(
+            Resp2Codec::parse_resp3("+h\u{e9}llo\r\n".as_bytes()),
+            Ok(Resp3Type::SimpleString("h\u{e9}llo".to_string()))
+        );
+
+        assert!(Resp2Codec::parse_bytes(b"+h\xffllo\r\n").is_err());
+    }
+
+
     #[test]
     fn test_valid_errors() {
         assert_eq!(
@@ -379,11 +926,11 @@ mod tests {
     fn test_valid_bulk_strings() {
         assert_eq!(
             Resp2Codec::parse("$5\r\nhello\r\n"),
-            Ok(Resp2Type::BulkString("hello".to_string()))
+            Ok(Resp2Type::BulkString(b"hello".to_vec()))
         );
         assert_eq!(
             Resp2Codec::parse("$0\r\n\r\n"),
-            Ok(Resp2Type::BulkString("".to_string()))
+            Ok(Resp2Type::BulkString(b"".to_vec()))
         );
 
         assert_eq!(
@@ -393,12 +940,12 @@ mod tests {
 
         assert_eq!(
             Resp2Codec::parse("$7\r\n!@#$%^&\r\n"),
-            Ok(Resp2Type::BulkString("!@#$%^&".to_string()))
+            Ok(Resp2Type::BulkString(b"!@#$%^&".to_vec()))
         );
 
         assert_eq!(
             Resp2Codec::parse("$4\r\n\x00\x01\x02\x03\r\n"),
-            Ok(Resp2Type::BulkString("\x00\x01\x02\x03".to_string()))
+            Ok(Resp2Type::BulkString(vec![0x00, 0x01, 0x02, 0x03]))
         );
     }
 
@@ -446,8 +993,8 @@ mod tests {
         assert_eq!(
             Resp2Codec::parse("*2\r\n$5\r\nhello\r\n$5\r\nworld\r\n"),
             Ok(Resp2Type::Array(vec![
-                Resp2Type::BulkString("hello".to_string()),
-                Resp2Type::BulkString("world".to_string())
+                Resp2Type::BulkString(b"hello".to_vec()),
+                Resp2Type::BulkString(b"world".to_vec())
             ]))
         );
 
@@ -458,7 +1005,7 @@ mod tests {
 
         assert_eq!(
             Resp2Codec::parse("*1\r\n$5\r\nhello\r\n"),
-            Ok(Resp2Type::Array(vec![Resp2Type::BulkString("hello".to_string())]))
+            Ok(Resp2Type::Array(vec![Resp2Type::BulkString(b"hello".to_vec())]))
         );
     }
 
@@ -524,7 +1071,7 @@ mod tests {
             Ok(Resp2Type::Array(vec![
                 Resp2Type::SimpleString("Simple".to_string()),
                 Resp2Type::Integer(42),
-                Resp2Type::BulkString("hello".to_string()),
+                Resp2Type::BulkString(b"hello".to_vec()),
                 Resp2Type::NullBulkString
             ]))
         );
@@ -565,4 +1112,312 @@ mod tests {
         let result = Resp2Codec::parse("*3\r\n+Simple\r\n$-2\r\n:42\r\n");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_parse_bytes_is_binary_safe() {
+        assert_eq!(
+            Resp2Codec::parse_bytes(b"$4\r\n\xff\xfe\x00\x01\r\n"),
+            Ok(Resp2Type::BulkString(vec![0xff, 0xfe, 0x00, 0x01]))
+        );
+
+        assert_eq!(
+            Resp2Codec::parse_bytes(b"$5\r\nhello\r\n"),
+            Ok(Resp2Type::BulkString(b"hello".to_vec()))
+        );
+
+        assert_eq!(
+            Resp2Codec::parse_bytes(b"*2\r\n$3\r\n\xed\xa0\x80\r\n$3\r\nfoo\r\n"),
+            Ok(Resp2Type::Array(vec![
+                Resp2Type::BulkString(vec![0xed, 0xa0, 0x80]),
+                Resp2Type::BulkString(b"foo".to_vec())
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_parse_bytes_invalid() {
+        assert!(Resp2Codec::parse_bytes(b"$3\r\nhello\r\n").is_err());
+        assert!(Resp2Codec::parse_bytes(b"$5\r\nhe").is_err());
+    }
+
+    #[test]
+    fn test_parse_agrees_with_parse_bytes_for_text_frames() {
+        assert_eq!(
+            Resp2Codec::parse("$5\r\nhello\r\n").unwrap(),
+            Resp2Codec::parse_bytes(b"$5\r\nhello\r\n").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_streaming_complete_frames() {
+        assert_eq!(
+            Resp2Codec::parse_streaming(b"$5\r\nhello\r\n"),
+            ParseResult::Complete { value: Resp2Type::BulkString(b"hello".to_vec()), consumed: 11 }
+        );
+
+        assert_eq!(
+            Resp2Codec::parse_streaming(b":42\r\nREMAINING"),
+            ParseResult::Complete { value: Resp2Type::Integer(42), consumed: 5 }
+        );
+
+        assert_eq!(
+            Resp2Codec::parse_streaming(b"*2\r\n:1\r\n:2\r\n"),
+            ParseResult::Complete {
+                value: Resp2Type::Array(vec![Resp2Type::Integer(1), Resp2Type::Integer(2)]),
+                consumed: 12
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_streaming_reports_exact_missing_bytes() {
+        assert_eq!(
+            Resp2Codec::parse_streaming(b"$5\r\nhello"),
+            ParseResult::Incomplete { needed: Needed::new(2) }
+        );
+
+        assert_eq!(
+            Resp2Codec::parse_streaming(b"$5\r\nhe"),
+            ParseResult::Incomplete { needed: Needed::new(5) }
+        );
+
+        assert_eq!(
+            Resp2Codec::parse_streaming(b"$5\r\n"),
+            ParseResult::Incomplete { needed: Needed::new(7) }
+        );
+    }
+
+    #[test]
+    fn test_parse_streaming_length_mismatch_is_invalid() {
+        assert!(matches!(
+            Resp2Codec::parse_streaming(b"$3\r\nhello\r\n"),
+            ParseResult::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_streaming_negative_bulk_string_length_is_invalid() {
+        assert!(matches!(
+            Resp2Codec::parse_streaming(b"$-2\r\nhello\r\n"),
+            ParseResult::Invalid(_)
+        ));
+
+        assert!(matches!(
+            Resp2Codec::parse_streaming(b"$-5\r\nhello\r\n"),
+            ParseResult::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_streaming_needs_one_more_byte_for_unterminated_text_frames() {
+        assert_eq!(
+            Resp2Codec::parse_streaming(b"+OK"),
+            ParseResult::Incomplete { needed: Needed::new(1) }
+        );
+    }
+
+    #[test]
+    fn test_encode_matches_wire_format() {
+        assert_eq!(Resp2Codec::encode(&Resp2Type::SimpleString("OK".to_string())), b"+OK\r\n".to_vec());
+
+        assert_eq!(
+            Resp2Codec::encode(&Resp2Type::SimpleError(Resp2SimpleError { kind: "ERR".to_string(), message: "unknown command".to_string() })),
+            b"-ERR unknown command\r\n".to_vec()
+        );
+
+        assert_eq!(Resp2Codec::encode(&Resp2Type::Integer(-42)), b":-42\r\n".to_vec());
+        assert_eq!(Resp2Codec::encode(&Resp2Type::BulkString(b"hello".to_vec())), b"$5\r\nhello\r\n".to_vec());
+        assert_eq!(Resp2Codec::encode(&Resp2Type::BulkString(vec![])), b"$0\r\n\r\n".to_vec());
+        assert_eq!(Resp2Codec::encode(&Resp2Type::NullBulkString), b"$-1\r\n".to_vec());
+        assert_eq!(Resp2Codec::encode(&Resp2Type::NullArray), b"*-1\r\n".to_vec());
+
+        assert_eq!(
+            Resp2Codec::encode(&Resp2Type::Array(vec![Resp2Type::Integer(1), Resp2Type::Integer(2)])),
+            b"*2\r\n:1\r\n:2\r\n".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_encode_then_parse_round_trips() {
+        let values = vec![
+            Resp2Type::SimpleString("PONG".to_string()),
+            Resp2Type::SimpleError(Resp2SimpleError { kind: "WRONGTYPE".to_string(), message: "Operation against a key holding the wrong kind of value".to_string() }),
+            Resp2Type::Integer(i64::MIN),
+            Resp2Type::Integer(i64::MAX),
+            Resp2Type::BulkString(vec![0xff, 0xfe, 0x00, 0x01]),
+            Resp2Type::NullBulkString,
+            Resp2Type::NullArray,
+            Resp2Type::Array(vec![]),
+            Resp2Type::Array(vec![
+                Resp2Type::BulkString(b"hello".to_vec()),
+                Resp2Type::Array(vec![Resp2Type::Integer(1), Resp2Type::NullBulkString]),
+                Resp2Type::SimpleString("Hello".to_string())
+            ])
+        ];
+
+        for value in values {
+            let encoded = Resp2Codec::encode(&value);
+            assert_eq!(Resp2Codec::parse_bytes(&encoded), Ok(value));
+        }
+    }
+
+    #[test]
+    fn test_resp3_still_understands_resp2_frames() {
+        assert_eq!(Resp2Codec::parse_resp3(b"+OK\r\n"), Ok(Resp3Type::SimpleString("OK".to_string())));
+        assert_eq!(Resp2Codec::parse_resp3(b":42\r\n"), Ok(Resp3Type::Integer(42)));
+        assert_eq!(Resp2Codec::parse_resp3(b"$5\r\nhello\r\n"), Ok(Resp3Type::BulkString(b"hello".to_vec())));
+        assert_eq!(Resp2Codec::parse_resp3(b"$-1\r\n"), Ok(Resp3Type::NullBulkString));
+        assert_eq!(Resp2Codec::parse_resp3(b"*-1\r\n"), Ok(Resp3Type::NullArray));
+    }
+
+    #[test]
+    fn test_resp3_null_and_boolean() {
+        assert_eq!(Resp2Codec::parse_resp3(b"_\r\n"), Ok(Resp3Type::Null));
+        assert_eq!(Resp2Codec::parse_resp3(b"#t\r\n"), Ok(Resp3Type::Boolean(true)));
+        assert_eq!(Resp2Codec::parse_resp3(b"#f\r\n"), Ok(Resp3Type::Boolean(false)));
+        assert!(Resp2Codec::parse_resp3(b"#x\r\n").is_err());
+    }
+
+    #[test]
+    fn test_resp3_double() {
+        assert_eq!(Resp2Codec::parse_resp3(b",2.5\r\n"), Ok(Resp3Type::Double(2.5)));
+        assert_eq!(Resp2Codec::parse_resp3(b",-1\r\n"), Ok(Resp3Type::Double(-1.0)));
+        assert_eq!(Resp2Codec::parse_resp3(b",inf\r\n"), Ok(Resp3Type::Double(f64::INFINITY)));
+        assert_eq!(Resp2Codec::parse_resp3(b",-inf\r\n"), Ok(Resp3Type::Double(f64::NEG_INFINITY)));
+        assert!(matches!(Resp2Codec::parse_resp3(b",nan\r\n"), Ok(Resp3Type::Double(n)) if n.is_nan()));
+    }
+
+    #[test]
+    fn test_resp3_big_number() {
+        assert_eq!(
+            Resp2Codec::parse_resp3(b"(3492890328409238509324850943850943825024385\r\n"),
+            Ok(Resp3Type::BigNumber("3492890328409238509324850943850943825024385".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_resp3_verbatim_string() {
+        assert_eq!(
+            Resp2Codec::parse_resp3(b"=15\r\ntxt:Some string\r\n"),
+            Ok(Resp3Type::VerbatimString { format: *b"txt", data: b"Some string".to_vec() })
+        );
+
+        assert!(Resp2Codec::parse_resp3(b"=2\r\ntx\r\n").is_err());
+    }
+
+    #[test]
+    fn test_resp3_blob_error() {
+        assert_eq!(
+            Resp2Codec::parse_resp3(b"!21\r\nSYNTAX invalid syntax\r\n"),
+            Ok(Resp3Type::BlobError(b"SYNTAX invalid syntax".to_vec()))
+        );
+    }
+
+    #[test]
+    fn test_resp3_map() {
+        assert_eq!(
+            Resp2Codec::parse_resp3(b"%2\r\n$3\r\nkey\r\n:1\r\n+flag\r\n#t\r\n"),
+            Ok(Resp3Type::Map(vec![
+                (Resp3Type::BulkString(b"key".to_vec()), Resp3Type::Integer(1)),
+                (Resp3Type::SimpleString("flag".to_string()), Resp3Type::Boolean(true))
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_resp3_set_and_push() {
+        assert_eq!(
+            Resp2Codec::parse_resp3(b"~2\r\n:1\r\n:2\r\n"),
+            Ok(Resp3Type::Set(vec![Resp3Type::Integer(1), Resp3Type::Integer(2)]))
+        );
+
+        assert_eq!(
+            Resp2Codec::parse_resp3(b">2\r\n+pubsub\r\n:1\r\n"),
+            Ok(Resp3Type::Push(vec![Resp3Type::SimpleString("pubsub".to_string()), Resp3Type::Integer(1)]))
+        );
+    }
+
+    #[test]
+    fn test_parse_inline_simple_commands() {
+        assert_eq!(
+            Resp2Codec::parse_inline(b"PING\r\n"),
+            Ok(Resp2Type::Array(vec![Resp2Type::BulkString(b"PING".to_vec())]))
+        );
+
+        assert_eq!(
+            Resp2Codec::parse_inline(b"SET key value\r\n"),
+            Ok(Resp2Type::Array(vec![
+                Resp2Type::BulkString(b"SET".to_vec()),
+                Resp2Type::BulkString(b"key".to_vec()),
+                Resp2Type::BulkString(b"value".to_vec())
+            ]))
+        );
+
+        assert_eq!(
+            Resp2Codec::parse_inline(b"SET  key\tvalue\r\n"),
+            Ok(Resp2Type::Array(vec![
+                Resp2Type::BulkString(b"SET".to_vec()),
+                Resp2Type::BulkString(b"key".to_vec()),
+                Resp2Type::BulkString(b"value".to_vec())
+            ]))
+        );
+
+        assert_eq!(Resp2Codec::parse_inline(b"\r\n"), Ok(Resp2Type::Array(vec![])));
+    }
+
+    #[test]
+    fn test_parse_inline_quoted_tokens() {
+        assert_eq!(
+            Resp2Codec::parse_inline(b"SET key \"hello world\"\r\n"),
+            Ok(Resp2Type::Array(vec![
+                Resp2Type::BulkString(b"SET".to_vec()),
+                Resp2Type::BulkString(b"key".to_vec()),
+                Resp2Type::BulkString(b"hello world".to_vec())
+            ]))
+        );
+
+        assert_eq!(
+            Resp2Codec::parse_inline(b"SET key \"line\\r\\none\\ttab\\x41\"\r\n"),
+            Ok(Resp2Type::Array(vec![
+                Resp2Type::BulkString(b"SET".to_vec()),
+                Resp2Type::BulkString(b"key".to_vec()),
+                Resp2Type::BulkString(b"line\r\none\ttabA".to_vec())
+            ]))
+        );
+
+        assert_eq!(
+            Resp2Codec::parse_inline(b"SET key 'raw \\n value'\r\n"),
+            Ok(Resp2Type::Array(vec![
+                Resp2Type::BulkString(b"SET".to_vec()),
+                Resp2Type::BulkString(b"key".to_vec()),
+                Resp2Type::BulkString(b"raw \\n value".to_vec())
+            ]))
+        );
+
+        assert_eq!(
+            Resp2Codec::parse_inline(b"SET key 'can\\'t stop'\r\n"),
+            Ok(Resp2Type::Array(vec![
+                Resp2Type::BulkString(b"SET".to_vec()),
+                Resp2Type::BulkString(b"key".to_vec()),
+                Resp2Type::BulkString(b"can't stop".to_vec())
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_parse_inline_invalid_commands() {
+        assert!(Resp2Codec::parse_inline(b"PING").is_err());
+        assert!(Resp2Codec::parse_inline(b"SET key \"unterminated\r\n").is_err());
+        assert!(Resp2Codec::parse_inline(b"SET key 'unterminated\r\n").is_err());
+        assert!(Resp2Codec::parse_inline(b"SET key \"bad escape \\q\"\r\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_inline_rejects_marker_prefixed_frames() {
+        assert!(Resp2Codec::parse_inline(b"*2\r\n$3\r\nfoo\r\n$3\r\nbar\r\n").is_err());
+        assert!(Resp2Codec::parse_inline(b"+OK\r\n").is_err());
+        assert!(Resp2Codec::parse_inline(b"-ERR oops\r\n").is_err());
+        assert!(Resp2Codec::parse_inline(b":42\r\n").is_err());
+        assert!(Resp2Codec::parse_inline(b"$5\r\nhello\r\n").is_err());
+    }
 }